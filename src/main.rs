@@ -1,26 +1,140 @@
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use sha2::{Sha256, Digest};
 use hex::encode as hex_encode;
+use secp256k1::{Secp256k1, SecretKey, PublicKey, Message};
+use secp256k1::ecdsa::Signature;
+
+// A compact, canonical on-disk/wire encoding for `Block`, `Transaction` and
+// `Blockchain`, loosely modelled on Bitcoin's network serialization: fixed-
+// width little-endian integers for scalars, a CompactSize-style varint for
+// lengths, and varint-prefixed vectors of nested values. Each type exposes
+// `serialize`/`deserialize` (whole-buffer, the public entry points) built on
+// top of a cursor-based `encode`/`decode` pair so nested values (a block's
+// transactions, a chain's blocks) can be read and written in place.
+
+#[derive(Debug)]
+enum SerializeError {
+    UnexpectedEof,
+    TrailingBytes,
+    InvalidUtf8,
+    InvalidPublicKey,
+    InvalidSignature,
+    UnknownFlag,
+    UnknownParent,
+    MissingTip,
+    Io(std::io::Error),
+    Invalid,
+}
+
+impl std::fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializeError::UnexpectedEof => write!(f, "unexpected end of input"),
+            SerializeError::TrailingBytes => write!(f, "unexpected trailing bytes after a valid value"),
+            SerializeError::InvalidUtf8 => write!(f, "field is not valid UTF-8"),
+            SerializeError::InvalidPublicKey => write!(f, "invalid secp256k1 public key"),
+            SerializeError::InvalidSignature => write!(f, "invalid ECDSA signature"),
+            SerializeError::UnknownFlag => write!(f, "unrecognized option flag"),
+            SerializeError::UnknownParent => write!(f, "block's previous_hash does not match any deserialized block"),
+            SerializeError::MissingTip => write!(f, "best tip hash is not among the deserialized blocks"),
+            SerializeError::Io(err) => write!(f, "I/O error: {}", err),
+            SerializeError::Invalid => write!(f, "deserialized chain failed validation"),
+        }
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl From<std::io::Error> for SerializeError {
+    fn from(err: std::io::Error) -> Self {
+        SerializeError::Io(err)
+    }
+}
+
+// CompactSize-style varint: values below 0xfd encode as a single byte;
+// larger values are prefixed with a marker byte (0xfd/0xfe/0xff) naming the
+// width of the little-endian integer that follows.
+fn write_varint(buf: &mut Vec<u8>, value: u64) {
+    if value < 0xfd {
+        buf.push(value as u8);
+    } else if value <= u16::MAX as u64 {
+        buf.push(0xfd);
+        buf.extend_from_slice(&(value as u16).to_le_bytes());
+    } else if value <= u32::MAX as u64 {
+        buf.push(0xfe);
+        buf.extend_from_slice(&(value as u32).to_le_bytes());
+    } else {
+        buf.push(0xff);
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn read_varint(bytes: &[u8], cursor: &mut usize) -> Result<u64, SerializeError> {
+    let marker = *bytes.get(*cursor).ok_or(SerializeError::UnexpectedEof)?;
+    *cursor += 1;
+    match marker {
+        0xfd => Ok(u16::from_le_bytes(read_exact(bytes, cursor, 2)?.try_into().unwrap()) as u64),
+        0xfe => Ok(u32::from_le_bytes(read_exact(bytes, cursor, 4)?.try_into().unwrap()) as u64),
+        0xff => Ok(u64::from_le_bytes(read_exact(bytes, cursor, 8)?.try_into().unwrap())),
+        small => Ok(small as u64),
+    }
+}
+
+// Advances `cursor` past the next `len` bytes and returns them, or reports
+// `UnexpectedEof` instead of panicking on a truncated buffer.
+fn read_exact<'a>(bytes: &'a [u8], cursor: &mut usize, len: usize) -> Result<&'a [u8], SerializeError> {
+    let end = cursor.checked_add(len).ok_or(SerializeError::UnexpectedEof)?;
+    let slice = bytes.get(*cursor..end).ok_or(SerializeError::UnexpectedEof)?;
+    *cursor = end;
+    Ok(slice)
+}
+
+fn write_bytes(buf: &mut Vec<u8>, data: &[u8]) {
+    write_varint(buf, data.len() as u64);
+    buf.extend_from_slice(data);
+}
+
+fn read_byte_vec(bytes: &[u8], cursor: &mut usize) -> Result<Vec<u8>, SerializeError> {
+    let len = read_varint(bytes, cursor)? as usize;
+    Ok(read_exact(bytes, cursor, len)?.to_vec())
+}
+
+fn write_string(buf: &mut Vec<u8>, value: &str) {
+    write_bytes(buf, value.as_bytes());
+}
+
+fn read_string(bytes: &[u8], cursor: &mut usize) -> Result<String, SerializeError> {
+    String::from_utf8(read_byte_vec(bytes, cursor)?).map_err(|_| SerializeError::InvalidUtf8)
+}
 
 #[derive(Debug, Clone)]
 struct Block {
     index: usize,
     timestamp: u64,
     transactions: Vec<Transaction>,
+    merkle_root: String,
     previous_hash: String,
     hash: String,
     nonce: u64,
+    target: [u8; 32],
 }
 
 impl Block {
-    fn new(index: usize, timestamp: u64, previous_hash: String, data: String) -> Self {
+    fn new(index: usize, timestamp: u64, previous_hash: String, transactions: Vec<Transaction>) -> Self {
+        let merkle_root = Transaction::create_merkle_root(&transactions);
+
         let mut block = Block {
             index,
             timestamp,
-            previous_hash: previous_hash.clone(),
+            transactions,
+            merkle_root,
+            previous_hash,
             hash: String::new(), // We'll calculate this later
-            data,
             nonce: 0,
+            // Difficulty 0 is exactly `MAX_TARGET`: every hash satisfies
+            // `check_pow` until `mine` assigns a real target.
+            target: target_from_difficulty_bits(0),
         };
 
         block.hash = block.calculate_hash();
@@ -28,96 +142,702 @@ impl Block {
     }
 
     fn calculate_hash(&self) -> String {
-        let headers = format!("{}:{}:{}:{}:{}", self.index, self.timestamp, self.previous_hash, self.data, self.nonce);
+        let headers = format!("{}:{}:{}:{}:{}", self.index, self.timestamp, self.previous_hash, self.merkle_root, self.nonce);
         let mut hasher = Sha256::new();
         hasher.update(headers);
         let result = hasher.finalize();
         hex_encode(result)
     }
 
-    fn mine(&mut self, difficulty: usize) {
+    fn mine(&mut self, target: [u8; 32]) {
+        self.target = target;
         loop {
             self.hash = self.calculate_hash();
-            if &self.hash[..difficulty] == &"0".repeat(difficulty) {
+            if self.check_pow() {
                 break;
             } else {
                 self.nonce += 1;
             }
         }
     }
+
+    // Interprets `hash` as a big-endian 256-bit integer and accepts it when it
+    // is at or below `target`, replacing the old whole-nibble prefix-zero
+    // check (which also panicked once `difficulty` exceeded the hash length).
+    // Analogous to the SPV proof-of-work check in the rust-bitcoin sources.
+    fn check_pow(&self) -> bool {
+        hash_to_bytes(&self.hash) <= self.target
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&(self.index as u64).to_le_bytes());
+        buf.extend_from_slice(&self.timestamp.to_le_bytes());
+        write_varint(buf, self.transactions.len() as u64);
+        for transaction in &self.transactions {
+            transaction.encode(buf);
+        }
+        write_string(buf, &self.merkle_root);
+        write_string(buf, &self.previous_hash);
+        write_string(buf, &self.hash);
+        buf.extend_from_slice(&self.nonce.to_le_bytes());
+        buf.extend_from_slice(&self.target);
+    }
+
+    fn decode(bytes: &[u8], cursor: &mut usize) -> Result<Self, SerializeError> {
+        let index = u64::from_le_bytes(read_exact(bytes, cursor, 8)?.try_into().unwrap()) as usize;
+        let timestamp = u64::from_le_bytes(read_exact(bytes, cursor, 8)?.try_into().unwrap());
+
+        let transaction_count = read_varint(bytes, cursor)?;
+        let mut transactions = Vec::with_capacity(transaction_count as usize);
+        for _ in 0..transaction_count {
+            transactions.push(Transaction::decode(bytes, cursor)?);
+        }
+
+        let merkle_root = read_string(bytes, cursor)?;
+        let previous_hash = read_string(bytes, cursor)?;
+        let hash = read_string(bytes, cursor)?;
+        let nonce = u64::from_le_bytes(read_exact(bytes, cursor, 8)?.try_into().unwrap());
+        let target: [u8; 32] = read_exact(bytes, cursor, 32)?.try_into().unwrap();
+
+        Ok(Block { index, timestamp, transactions, merkle_root, previous_hash, hash, nonce, target })
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode(&mut buf);
+        buf
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let mut cursor = 0;
+        let block = Block::decode(bytes, &mut cursor)?;
+        if cursor != bytes.len() {
+            return Err(SerializeError::TrailingBytes);
+        }
+        Ok(block)
+    }
+}
+
+// The easiest possible target (all ones): every valid hash satisfies
+// `hash <= target`, used for blocks that aren't subject to mining, like
+// the genesis block before `mine` assigns it a real target.
+const MAX_TARGET: [u8; 32] = [0xff; 32];
+
+fn hash_to_bytes(hash: &str) -> [u8; 32] {
+    let bytes = hex::decode(hash).expect("block hash must be valid hex");
+    bytes.try_into().expect("SHA-256 hash must be 32 bytes")
+}
+
+// Builds a target of the form `u256::MAX >> difficulty_bits`: each additional
+// bit of difficulty halves the space of hashes that satisfy `check_pow`.
+fn target_from_difficulty_bits(difficulty_bits: u32) -> [u8; 32] {
+    let bits = difficulty_bits as usize;
+    if bits >= 256 {
+        return [0u8; 32];
+    }
+
+    let byte_shift = bits / 8;
+    let bit_shift = bits % 8;
+    let mut target = [0u8; 32];
+
+    // `target[i]` is built from `MAX_TARGET[i - byte_shift]` (and its
+    // predecessor, for the carried-over bits), so this isn't a plain
+    // iteration over one slice that `.iter()` could express.
+    #[allow(clippy::needless_range_loop)]
+    for i in 0..32 {
+        if i < byte_shift {
+            continue;
+        }
+        let src_index = i - byte_shift;
+        let mut value = MAX_TARGET[src_index] >> bit_shift;
+        if bit_shift > 0 && src_index > 0 {
+            value |= MAX_TARGET[src_index - 1] << (8 - bit_shift);
+        }
+        target[i] = value;
+    }
+
+    target
+}
+
+// How often, in seconds, a block is expected to be mined.
+const TARGET_BLOCK_INTERVAL_SECS: u64 = 10;
+// Retarget the difficulty every this many blocks, based on how long that
+// window actually took versus the expected `RETARGET_WINDOW *
+// TARGET_BLOCK_INTERVAL_SECS`.
+const RETARGET_WINDOW: usize = 10;
+
+// How many preceding blocks feed the Median Time Past calculation.
+const MEDIAN_TIME_PAST_SPAN: usize = 11;
+// How far into the future, relative to the local clock, a block's timestamp
+// may claim to be before it's rejected outright.
+const FUTURE_TIME_LIMIT_SECS: u64 = 7200;
+
+// Scales a 256-bit target by `multiplier`, saturating at `MAX_TARGET` on
+// overflow. Operates on the big-endian byte array directly since the target
+// never needs more precision than that.
+fn mul_u64(target: &[u8; 32], multiplier: u64) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    let mut carry: u128 = 0;
+    for i in (0..32).rev() {
+        let product = target[i] as u128 * multiplier as u128 + carry;
+        result[i] = (product & 0xff) as u8;
+        carry = product >> 8;
+    }
+    if carry > 0 {
+        return MAX_TARGET;
+    }
+    result
+}
+
+fn div_u64(target: &[u8; 32], divisor: u64) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    let mut remainder: u128 = 0;
+    for i in 0..32 {
+        let current = (remainder << 8) | target[i] as u128;
+        result[i] = (current / divisor as u128) as u8;
+        remainder = current % divisor as u128;
+    }
+    result
+}
+
+// Bitcoin-style retarget: scale `old_target` by `actual_timespan /
+// expected_timespan`, clamped to at most a 4x swing in either direction per
+// retarget so a handful of outlier timestamps can't blow the difficulty out
+// in one step.
+fn retarget_target(old_target: [u8; 32], actual_timespan: u64, expected_timespan: u64) -> [u8; 32] {
+    let min_timespan = (expected_timespan / 4).max(1);
+    let max_timespan = expected_timespan * 4;
+    let clamped_timespan = actual_timespan.clamp(min_timespan, max_timespan);
+
+    let scaled = mul_u64(&old_target, clamped_timespan);
+    div_u64(&scaled, expected_timespan)
+}
+
+// Reads a 256-bit target's bytes as a plain magnitude, most significant byte
+// first. Used only to rank targets against each other when tallying work; an
+// f64 loses precision at the low end, but that's fine since we only ever sum
+// and compare these, never feed them back into exact target arithmetic.
+fn target_to_f64(target: &[u8; 32]) -> f64 {
+    target.iter().fold(0f64, |acc, &byte| acc * 256.0 + byte as f64)
+}
+
+// A block's proof-of-work contribution to its branch's total work, defined
+// (as in Bitcoin) as the inverse of its target: a smaller target means fewer
+// hashes satisfy it, so finding one represents more work.
+fn block_work(target: &[u8; 32]) -> f64 {
+    1.0 / target_to_f64(target).max(1.0)
+}
+
+// Deterministic secp256k1 keypair for a given identity name. Real wallets
+// derive keys from secure randomness; this toy chain only needs reproducible
+// addresses so demo code and tests can refer to "alice" or "bob" without
+// threading key material everywhere.
+fn keypair_from_seed(seed: &str) -> (SecretKey, PublicKey) {
+    let secp = Secp256k1::new();
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    let digest: [u8; 32] = hasher.finalize().into();
+    let secret_key = SecretKey::from_byte_array(digest).expect("seed hash is a valid secp256k1 scalar");
+    let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+    (secret_key, public_key)
+}
+
+// The issuing identity used for coinbase-style transactions that create
+// value rather than spend an existing balance (the genesis transaction, and
+// any later "mint" transactions funding a demo address). Exempted from the
+// spend check in `validate_transactions`, the same way a coinbase input has
+// no prior balance to check against.
+fn network_keypair() -> (SecretKey, PublicKey) {
+    keypair_from_seed("network")
+}
+
+// Applies `transaction` to `balances`, debiting the sender and crediting the
+// receiver. Addresses are seen for the first time with an implicit balance
+// of zero.
+fn apply_transaction(balances: &mut HashMap<PublicKey, f32>, transaction: &Transaction) {
+    *balances.entry(transaction.sender).or_insert(0.0) -= transaction.amount;
+    *balances.entry(transaction.receiver).or_insert(0.0) += transaction.amount;
+}
+
+// Validates every transaction in `block` against `balances` (mutating it as
+// transactions are applied, so a later transaction in the same block sees
+// the effect of an earlier one), rejecting the block outright if any
+// transaction is unsigned, badly signed, claims a negative amount, or spends
+// more than its sender currently holds. `network_key` is exempt from the
+// balance check since it issues value instead of spending an existing one.
+fn validate_transactions(block: &Block, balances: &mut HashMap<PublicKey, f32>, network_key: &PublicKey) -> bool {
+    for transaction in &block.transactions {
+        if !transaction.verify_signature() {
+            return false;
+        }
+        if transaction.amount < 0.0 {
+            return false;
+        }
+        if transaction.sender != *network_key {
+            let sender_balance = *balances.get(&transaction.sender).unwrap_or(&0.0);
+            if transaction.amount > sender_balance {
+                return false;
+            }
+        }
+        apply_transaction(balances, transaction);
+    }
+    true
 }
 
 #[derive(Debug)]
 struct Blockchain {
-    chain: Vec<Block>,
+    // Every block this node knows about, keyed by its own hash. Parent links
+    // live inside `Block::previous_hash`, so this is the hash-keyed block
+    // tree: any block whose parent is already present can be attached here,
+    // including ones that aren't on the currently active branch.
+    blocks: HashMap<String, Block>,
+    // Cumulative proof-of-work (sum of `block_work`) from genesis to each
+    // block, keyed by that block's hash. Lets `accept_block` compare branches
+    // without re-walking them on every call.
+    cumulative_work: HashMap<String, f64>,
+    // Hash of the tip with the greatest cumulative work, i.e. the root of the
+    // currently active chain.
+    best_tip: String,
 }
 
 impl Blockchain {
     fn new() -> Self {
-        let genesis_block = Block::new(0, now(), String::from("0"), String::from("Genesis Block"));
+        let (network_secret, network_key) = network_keypair();
+        let (_, genesis_key) = keypair_from_seed("genesis");
+        let mut genesis_transaction = Transaction {
+            sender: network_key,
+            receiver: genesis_key,
+            amount: 0.0,
+            signature: None,
+        };
+        genesis_transaction.sign(&network_secret);
+
+        let genesis_block = Block::new(0, now(), String::from("0"), vec![genesis_transaction]);
+        let genesis_hash = genesis_block.hash.clone();
+        let genesis_work = block_work(&genesis_block.target);
+
+        let mut blocks = HashMap::new();
+        blocks.insert(genesis_hash.clone(), genesis_block);
+        let mut cumulative_work = HashMap::new();
+        cumulative_work.insert(genesis_hash.clone(), genesis_work);
+
         Blockchain {
-            chain: vec![genesis_block],
+            blocks,
+            cumulative_work,
+            best_tip: genesis_hash,
+        }
+    }
+
+    // Walks parent pointers from `hash` back to genesis and returns the
+    // resulting chain in genesis-first order, so it can be fed to the
+    // height-indexed helpers below (`target_for_height`, `median_time_past`,
+    // ...) exactly as if it were still one flat `Vec<Block>`.
+    fn path_to(&self, hash: &str) -> Vec<Block> {
+        let mut path = Vec::new();
+        let mut current = hash.to_string();
+        loop {
+            let block = self.blocks.get(&current).expect("path_to requires a known block hash");
+            path.push(block.clone());
+            if block.index == 0 {
+                break;
+            }
+            current = block.previous_hash.clone();
         }
+        path.reverse();
+        path
     }
 
-    fn add_block(&mut self, data: String) {
+    // The block tip with the greatest cumulative work, i.e. the head of the
+    // currently active chain.
+    fn best_tip(&self) -> &Block {
+        &self.blocks[&self.best_tip]
+    }
+
+    // The currently active chain, genesis first, as chosen by cumulative
+    // work rather than recency — the Nakamoto consensus rule.
+    fn active_chain(&self) -> Vec<Block> {
+        self.path_to(&self.best_tip)
+    }
+
+    // Attaches `block` to the tree if it links to a known parent and passes
+    // the same validity checks `validate_chain` runs, then re-evaluates
+    // whether it should become the new best tip. Returns whether accepting it
+    // caused a reorg, i.e. the active chain switched away from a branch the
+    // previous best tip was on (a block that simply extends the current tip
+    // is not a reorg, even though the tip hash changes).
+    fn accept_block(&mut self, block: Block) -> bool {
+        if !self.blocks.contains_key(&block.previous_hash) {
+            return false; // orphan: parent not (yet) known
+        }
+
+        let mut candidate_chain = self.path_to(&block.previous_hash);
+        if block.index != candidate_chain.len() {
+            return false;
+        }
+        candidate_chain.push(block.clone());
+        if !validate_linear_chain(&candidate_chain) {
+            return false;
+        }
+
+        let parent_hash = block.previous_hash.clone();
+        let hash = block.hash.clone();
+        let work = self.cumulative_work[&parent_hash] + block_work(&block.target);
+
+        self.blocks.insert(hash.clone(), block);
+        self.cumulative_work.insert(hash.clone(), work);
+
+        let is_new_best = work > self.cumulative_work[&self.best_tip];
+        let is_reorg = is_new_best && parent_hash != self.best_tip;
+        if is_new_best {
+            self.best_tip = hash;
+        }
+        is_reorg
+    }
+
+    // The target a block at `height` must be mined against, derived only
+    // from blocks already in `chain` so validators can recompute it
+    // identically to the miner. Every `RETARGET_WINDOW` blocks this
+    // retargets from how long that window actually took to mine.
+    fn target_for_height(chain: &[Block], height: usize) -> [u8; 32] {
+        if height == 0 {
+            return MAX_TARGET;
+        }
+        if height < RETARGET_WINDOW || !height.is_multiple_of(RETARGET_WINDOW) {
+            return chain[height - 1].target;
+        }
+
+        let tip = &chain[height - 1];
+        let window_start = &chain[height - RETARGET_WINDOW];
+        let actual_timespan = tip.timestamp.saturating_sub(window_start.timestamp);
+        let expected_timespan = RETARGET_WINDOW as u64 * TARGET_BLOCK_INTERVAL_SECS;
+
+        retarget_target(tip.target, actual_timespan, expected_timespan)
+    }
+
+    // Median of the previous `MEDIAN_TIME_PAST_SPAN` blocks' timestamps
+    // before `height`. Requiring new timestamps to exceed this (rather than
+    // just "not earlier than the previous block") stops an adversary from
+    // manipulating the MTP by backdating a single block.
+    fn median_time_past(chain: &[Block], height: usize) -> u64 {
+        let window_start = height.saturating_sub(MEDIAN_TIME_PAST_SPAN);
+        let mut timestamps: Vec<u64> = chain[window_start..height].iter().map(|b| b.timestamp).collect();
+        timestamps.sort_unstable();
+        timestamps[timestamps.len() / 2]
+    }
+
+    // Consensus timestamp rule: `MTP < timestamp < now() + FTL`. The lower
+    // bound defeats MTP manipulation (pushing timestamps forward to make
+    // honest blocks look backdated); the upper bound keeps blocks from
+    // claiming to be from the future.
+    fn validate_timestamp(chain: &[Block], height: usize, timestamp: u64) -> bool {
+        if height == 0 {
+            return true; // genesis has no history to compare against
+        }
+
+        let mtp = Blockchain::median_time_past(chain, height);
+        timestamp > mtp && timestamp < now() + FUTURE_TIME_LIMIT_SECS
+    }
+
+    // Clamps the local clock forward just enough to satisfy the MTP rule, so
+    // blocks mined faster than one wall-clock second apart (routine at low
+    // difficulty) don't trip the consensus check.
+    fn next_valid_timestamp(chain: &[Block], height: usize) -> u64 {
+        if height == 0 {
+            return now();
+        }
+        let mtp = Blockchain::median_time_past(chain, height);
+        now().max(mtp + 1)
+    }
+
+    fn add_block(&mut self, transactions: Vec<Transaction>) {
+        let active_chain = self.active_chain();
+        let height = active_chain.len();
+        let target = Blockchain::target_for_height(&active_chain, height);
+        let timestamp = Blockchain::next_valid_timestamp(&active_chain, height);
+
         let mut new_block = Block::new(
-            self.chain.len(),
-            now(),
-            self.chain.last().unwrap().hash.clone(),
-            data,
+            height,
+            timestamp,
+            self.best_tip.clone(),
+            transactions,
         );
 
-        new_block.mine(4); // Assuming a difficulty of 4 leading zeros
-        self.chain.push(new_block);
+        new_block.mine(target);
+        self.accept_block(new_block);
     }
 
     fn validate_chain(&self) -> bool {
-        if self.chain.len() < 2 {
-            return true;
+        validate_linear_chain(&self.active_chain())
+    }
+
+    // Serializes every block this node knows about (not just the active
+    // chain, so forks survive a round trip), sorted by `(index, hash)` for a
+    // deterministic encoding, followed by the best tip's hash. Cumulative
+    // work is intentionally not stored: it's re-derived on load from each
+    // block's own target, the same way `accept_block` computes it.
+    fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        let mut blocks: Vec<&Block> = self.blocks.values().collect();
+        blocks.sort_by(|a, b| (a.index, &a.hash).cmp(&(b.index, &b.hash)));
+
+        write_varint(&mut buf, blocks.len() as u64);
+        for block in blocks {
+            block.encode(&mut buf);
         }
+        write_string(&mut buf, &self.best_tip);
 
-        for i in 1..self.chain.len() {
-            let current_block = &self.chain[i];
-            let previous_block = &self.chain[i - 1];
+        buf
+    }
 
-            // Recalculate the current block's hash (considering its transactions)
-            // and ensure it matches the stored hash.
-            if current_block.hash != current_block.calculate_hash() {
-                return false;
-            }
+    fn deserialize(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let mut cursor = 0;
 
-            // Check if the current block's previous hash matches the previous block's hash
-            if current_block.previous_hash != previous_block.hash {
-                return false;
-            }
+        let block_count = read_varint(bytes, &mut cursor)?;
+        let mut by_height = Vec::with_capacity(block_count as usize);
+        for _ in 0..block_count {
+            by_height.push(Block::decode(bytes, &mut cursor)?);
+        }
+        let best_tip = read_string(bytes, &mut cursor)?;
 
-            // Here, you would also verify the Merkle root if it were explicitly stored and used
-            // This example does not yet include a Merkle root in the block structure.
+        if cursor != bytes.len() {
+            return Err(SerializeError::TrailingBytes);
         }
 
-        true
+        // Every block's parent is one height below it, so processing in
+        // ascending `index` order guarantees a block's parent work has
+        // already been computed before the block itself is reached.
+        by_height.sort_by_key(|block| block.index);
+
+        let mut blocks = HashMap::new();
+        let mut cumulative_work = HashMap::new();
+        for block in by_height {
+            let work = if block.index == 0 {
+                block_work(&block.target)
+            } else {
+                let parent_work = *cumulative_work.get(&block.previous_hash).ok_or(SerializeError::UnknownParent)?;
+                parent_work + block_work(&block.target)
+            };
+            cumulative_work.insert(block.hash.clone(), work);
+            blocks.insert(block.hash.clone(), block);
+        }
+
+        if !blocks.contains_key(&best_tip) {
+            return Err(SerializeError::MissingTip);
+        }
+
+        Ok(Blockchain { blocks, cumulative_work, best_tip })
+    }
+
+    fn save_to_file(&self, path: &str) -> Result<(), SerializeError> {
+        std::fs::write(path, self.serialize())?;
+        Ok(())
+    }
+
+    // Re-runs `validate_chain` after loading so a corrupted or tampered file
+    // is rejected rather than silently accepted as the new chain state.
+    fn load_from_file(path: &str) -> Result<Self, SerializeError> {
+        let bytes = std::fs::read(path)?;
+        let blockchain = Blockchain::deserialize(&bytes)?;
+        if !blockchain.validate_chain() {
+            return Err(SerializeError::Invalid);
+        }
+        Ok(blockchain)
     }
 }
 
+// The consensus checks a linear chain (genesis-first) must pass: every
+// block's hash and previous-hash link are intact, its Merkle root matches its
+// transactions, it was mined against the target the retargeting schedule
+// expects at its height, its timestamp satisfies the MTP/FTL rule, it
+// actually satisfies its own proof-of-work target, and every transaction in
+// it is properly signed and affordable. Shared by `validate_chain` (checking
+// the whole active chain) and `accept_block` (checking one new block against
+// the chain leading up to its parent).
+fn validate_linear_chain(chain: &[Block]) -> bool {
+    if chain.is_empty() {
+        return true;
+    }
+
+    // Genesis isn't covered by the loop below (which starts at height 1), so
+    // its transactions are run through the same signature/amount/balance
+    // checks here — otherwise a tampered file could substitute a genesis
+    // block that mints an arbitrary balance to an attacker-controlled key.
+    let (_, network_key) = network_keypair();
+    let mut balances = HashMap::new();
+    if !validate_transactions(&chain[0], &mut balances, &network_key) {
+        return false;
+    }
+
+    if chain.len() < 2 {
+        return true;
+    }
+
+    for i in 1..chain.len() {
+        let current_block = &chain[i];
+        let previous_block = &chain[i - 1];
+
+        // Recalculate the current block's hash (considering its transactions)
+        // and ensure it matches the stored hash.
+        if current_block.hash != current_block.calculate_hash() {
+            return false;
+        }
+
+        // Check if the current block's previous hash matches the previous block's hash
+        if current_block.previous_hash != previous_block.hash {
+            return false;
+        }
+
+        // Recompute the Merkle root from the block's transactions so that a
+        // tampered transaction list is caught even if someone re-mined `hash`
+        // against the altered (but not re-rooted) data.
+        if current_block.merkle_root != Transaction::create_merkle_root(&current_block.transactions) {
+            return false;
+        }
+
+        // Reject blocks mined below the difficulty the retargeting
+        // schedule actually expects at this height.
+        if current_block.target != Blockchain::target_for_height(chain, i) {
+            return false;
+        }
+
+        // Reject backdated or far-future timestamps (MTP/FTL rule).
+        if !Blockchain::validate_timestamp(chain, i, current_block.timestamp) {
+            return false;
+        }
+
+        // Every mined block must actually satisfy its own proof-of-work target.
+        if !current_block.check_pow() {
+            return false;
+        }
+
+        // Every transaction must be properly signed by its claimed sender and
+        // affordable given the ledger built up so far.
+        if !validate_transactions(current_block, &mut balances, &network_key) {
+            return false;
+        }
+    }
+
+    true
+}
+
 #[derive(Debug, Clone)]
 struct Transaction {
-    sender: String,
-    receiver: String,
+    sender: PublicKey,
+    receiver: PublicKey,
     amount: f32,
+    signature: Option<Signature>,
 }
 
 impl Transaction {
+    // The bytes `sign` and `verify_signature` operate over: sender, receiver
+    // and amount, but never the signature itself (which doesn't exist yet
+    // when signing, and shouldn't be part of what's being attested to).
+    fn signing_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.sender.serialize());
+        hasher.update(self.receiver.serialize());
+        hasher.update(self.amount.to_be_bytes());
+        hasher.finalize().into()
+    }
+
+    // Signs this transaction's `signing_hash` with `private_key`, proving
+    // `sender` authorized the transfer.
+    fn sign(&mut self, private_key: &SecretKey) {
+        let secp = Secp256k1::new();
+        let message = Message::from_digest(self.signing_hash());
+        self.signature = Some(secp.sign_ecdsa(message, private_key));
+    }
+
+    // Checks that `signature` is present and is a valid ECDSA signature by
+    // `sender` over this transaction's `signing_hash`.
+    fn verify_signature(&self) -> bool {
+        let Some(signature) = &self.signature else {
+            return false;
+        };
+        let secp = Secp256k1::new();
+        let message = Message::from_digest(self.signing_hash());
+        secp.verify_ecdsa(message, signature, &self.sender).is_ok()
+    }
+
+    fn transaction_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.sender.serialize());
+        hasher.update(self.receiver.serialize());
+        hasher.update(self.amount.to_be_bytes());
+        if let Some(signature) = &self.signature {
+            hasher.update(signature.serialize_compact());
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    // `sender`/`receiver` are fixed-width (33-byte compressed secp256k1
+    // points), so only `signature` needs a presence flag rather than a
+    // length prefix.
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.sender.serialize());
+        buf.extend_from_slice(&self.receiver.serialize());
+        buf.extend_from_slice(&self.amount.to_le_bytes());
+        match &self.signature {
+            Some(signature) => {
+                buf.push(1);
+                buf.extend_from_slice(&signature.serialize_compact());
+            }
+            None => buf.push(0),
+        }
+    }
+
+    fn decode(bytes: &[u8], cursor: &mut usize) -> Result<Self, SerializeError> {
+        let sender = PublicKey::from_slice(read_exact(bytes, cursor, 33)?)
+            .map_err(|_| SerializeError::InvalidPublicKey)?;
+        let receiver = PublicKey::from_slice(read_exact(bytes, cursor, 33)?)
+            .map_err(|_| SerializeError::InvalidPublicKey)?;
+        let amount = f32::from_le_bytes(read_exact(bytes, cursor, 4)?.try_into().unwrap());
+
+        let flag = *bytes.get(*cursor).ok_or(SerializeError::UnexpectedEof)?;
+        *cursor += 1;
+        let signature = match flag {
+            0 => None,
+            1 => Some(
+                Signature::from_compact(read_exact(bytes, cursor, 64)?)
+                    .map_err(|_| SerializeError::InvalidSignature)?,
+            ),
+            _ => return Err(SerializeError::UnknownFlag),
+        };
+
+        Ok(Transaction { sender, receiver, amount, signature })
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode(&mut buf);
+        buf
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let mut cursor = 0;
+        let transaction = Transaction::decode(bytes, &mut cursor)?;
+        if cursor != bytes.len() {
+            return Err(SerializeError::TrailingBytes);
+        }
+        Ok(transaction)
+    }
+
+    // Folds the transaction hashes up into a single root. When a level has an
+    // odd number of nodes, the lone trailing hash is promoted as-is rather than
+    // duplicated against itself, which avoids the Merkle duplication
+    // malleability described in CVE-2012-2459.
     fn create_merkle_root(transactions: &[Transaction]) -> String {
         let mut hashes = transactions.iter()
-            .map(|transaction| {
-                let transaction_data = format!("{:?}{:?}{:?}", transaction.sender, transaction.receiver, transaction.amount);
-                let mut hasher = Sha256::new();
-                hasher.update(transaction_data);
-                hex::encode(hasher.finalize())
-            })
+            .map(Transaction::transaction_hash)
             .collect::<Vec<String>>();
-    
+
+        // No transactions to fold means no tree to build; "0" is the same
+        // sentinel `Block::new`'s genesis caller uses for "no real hash here".
+        if hashes.is_empty() {
+            return String::from("0");
+        }
+
         while hashes.len() > 1 {
             let mut temp_hashes = Vec::new();
             for i in (0..hashes.len()).step_by(2) {
@@ -132,10 +852,85 @@ impl Transaction {
             }
             hashes = temp_hashes;
         }
-    
+
         hashes[0].clone()
     }
-    
+
+    // Builds an authentication path for `transactions[index]`: the sibling
+    // hash (and its left/right position) at each level on the way up to the
+    // root, mirroring the pairing done in `create_merkle_root`. A level with
+    // no sibling (the tracked node is an odd-one-out promoted unchanged)
+    // records `None` so `verify_proof` knows to skip combining at that step.
+    fn generate_merkle_proof(transactions: &[Transaction], index: usize) -> Vec<MerkleProofStep> {
+        let mut hashes = transactions.iter()
+            .map(Transaction::transaction_hash)
+            .collect::<Vec<String>>();
+        let mut tracked = index;
+        let mut proof = Vec::new();
+
+        while hashes.len() > 1 {
+            let mut temp_hashes = Vec::new();
+            for i in (0..hashes.len()).step_by(2) {
+                if i + 1 < hashes.len() {
+                    let combined = format!("{}{}", hashes[i], hashes[i + 1]);
+                    let mut hasher = Sha256::new();
+                    hasher.update(combined);
+                    temp_hashes.push(hex::encode(hasher.finalize()));
+
+                    if tracked == i {
+                        proof.push(MerkleProofStep { sibling: Some(hashes[i + 1].clone()), sibling_is_left: false });
+                    } else if tracked == i + 1 {
+                        proof.push(MerkleProofStep { sibling: Some(hashes[i].clone()), sibling_is_left: true });
+                    }
+                } else {
+                    temp_hashes.push(hashes[i].clone());
+
+                    if tracked == i {
+                        proof.push(MerkleProofStep { sibling: None, sibling_is_left: false });
+                    }
+                }
+            }
+            tracked /= 2;
+            hashes = temp_hashes;
+        }
+
+        proof
+    }
+}
+
+// One step of a Merkle authentication path: the sibling hash to combine with
+// the node currently being folded upward, and whether that sibling sits to
+// its left or right. `sibling: None` means this level had no pair (the
+// tracked node was promoted unchanged), so the running hash passes through.
+#[derive(Debug, Clone)]
+struct MerkleProofStep {
+    sibling: Option<String>,
+    sibling_is_left: bool,
+}
+
+// SPV-style verification: fold `tx_hash` upward through the recorded
+// siblings and check the result against the block's `merkle_root`, without
+// needing the rest of the transactions.
+fn verify_proof(tx_hash: &str, proof: &[MerkleProofStep], root: &str) -> bool {
+    let mut current = tx_hash.to_string();
+
+    for step in proof {
+        current = match &step.sibling {
+            Some(sibling) => {
+                let combined = if step.sibling_is_left {
+                    format!("{}{}", sibling, current)
+                } else {
+                    format!("{}{}", current, sibling)
+                };
+                let mut hasher = Sha256::new();
+                hasher.update(combined);
+                hex::encode(hasher.finalize())
+            }
+            None => current,
+        };
+    }
+
+    current == root
 }
 
 fn now() -> u64 {
@@ -143,31 +938,469 @@ fn now() -> u64 {
 }
 
 fn main() {
+    let (network_secret, network_key) = network_keypair();
+    let (alice_secret, alice_key) = keypair_from_seed("alice");
+    let (bob_secret, bob_key) = keypair_from_seed("bob");
+    let (_, carol_key) = keypair_from_seed("carol");
+
     let mut blockchain = Blockchain::new();
-    blockchain.add_block(String::from("Block 1"));
-    blockchain.add_block(String::from("Block 2"));
+
+    let mut fund_alice = Transaction { sender: network_key, receiver: alice_key, amount: 10.0, signature: None };
+    fund_alice.sign(&network_secret);
+    blockchain.add_block(vec![fund_alice]);
+
+    let mut alice_to_bob = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+    alice_to_bob.sign(&alice_secret);
+    blockchain.add_block(vec![alice_to_bob]);
+
+    let mut bob_to_carol = Transaction { sender: bob_key, receiver: carol_key, amount: 2.0, signature: None };
+    bob_to_carol.sign(&bob_secret);
+    blockchain.add_block(vec![bob_to_carol]);
+
+    blockchain.save_to_file("blockchain.dat").expect("failed to save blockchain to disk");
+    let reloaded = Blockchain::load_from_file("blockchain.dat").expect("failed to load blockchain from disk");
+
+    println!("best tip: {}", reloaded.best_tip().hash);
+
+    // Demonstrate SPV-style verification: a light client holding only the
+    // tip block's Merkle root can confirm a transaction was included without
+    // the rest of the block.
+    let tip = reloaded.active_chain().last().expect("chain should have a tip").clone();
+    let proof = Transaction::generate_merkle_proof(&tip.transactions, 0);
+    let included = verify_proof(&tip.transactions[0].transaction_hash(), &proof, &tip.merkle_root);
+    println!("transaction included in tip: {}", included);
+
+    // Demonstrate the standalone wire format for a single transaction, the
+    // same encoding `Block`/`Blockchain` use for their nested transactions.
+    let wire_bytes = tip.transactions[0].serialize();
+    let from_wire = Transaction::deserialize(&wire_bytes).expect("transaction should round-trip");
+    println!("transaction round-trip verifies: {}", from_wire.verify_signature());
+
+    // Same round trip for the whole tip block, the entry point a single
+    // block would use to cross the wire outside the whole-chain `save_to_file`/
+    // `load_from_file` path.
+    let block_wire_bytes = tip.serialize();
+    let block_from_wire = Block::deserialize(&block_wire_bytes).expect("block should round-trip");
+    println!("block round-trip hash matches: {}", block_from_wire.hash == tip.hash);
 
     // Placeholder to display the blockchain
-    println!("{:?}", blockchain.chain);
+    println!("{:?}", reloaded.active_chain());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn sample_transactions() -> Vec<Transaction> {
+        let (network_secret, network_key) = network_keypair();
+        let (_, alice_key) = keypair_from_seed("alice");
+        let mut transaction = Transaction { sender: network_key, receiver: alice_key, amount: 1.0, signature: None };
+        transaction.sign(&network_secret);
+        vec![transaction]
+    }
+
     #[test]
     fn test_mining() {
-        let mut block = Block::new(0, now(), String::from("0"), String::from("Test Block"));
-        block.mine(2); // Adjust difficulty as needed
+        let mut block = Block::new(0, now(), String::from("0"), sample_transactions());
+        block.mine(target_from_difficulty_bits(8)); // Adjust difficulty as needed
+        assert!(block.check_pow(), "Block wasn't mined correctly: {}", block.hash);
         assert!(block.hash.starts_with("00"), "Block wasn't mined correctly: {}", block.hash);
     }
 
+    #[test]
+    fn test_check_pow_rejects_hash_above_target() {
+        let mut block = Block::new(0, now(), String::from("0"), sample_transactions());
+        block.target = target_from_difficulty_bits(250); // an all-but-impossible target
+        assert!(!block.check_pow());
+    }
+
+    // Builds a synthetic, unmined chain of `RETARGET_WINDOW` blocks so the
+    // retargeting arithmetic can be tested directly against known timestamps,
+    // without paying for real mining.
+    fn synthetic_chain_with_spacing(baseline: [u8; 32], spacing_secs: u64) -> Vec<Block> {
+        (0..RETARGET_WINDOW)
+            .map(|i| {
+                let mut block = Block::new(i, i as u64 * spacing_secs, String::from("0"), sample_transactions());
+                block.target = baseline;
+                block
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_retarget_tightens_when_blocks_come_too_fast() {
+        let baseline = target_from_difficulty_bits(32);
+        let chain = synthetic_chain_with_spacing(baseline, 1); // 1s spacing, want 10s
+
+        let next_target = Blockchain::target_for_height(&chain, RETARGET_WINDOW);
+        assert!(next_target < baseline, "target should shrink (harder) when blocks were mined too fast");
+    }
+
+    #[test]
+    fn test_retarget_loosens_when_blocks_come_too_slow() {
+        let baseline = target_from_difficulty_bits(32);
+        let chain = synthetic_chain_with_spacing(baseline, 1000); // 1000s spacing, want 10s
+
+        let next_target = Blockchain::target_for_height(&chain, RETARGET_WINDOW);
+        assert!(next_target > baseline, "target should grow (easier) when blocks were mined too slowly");
+    }
+
+    #[test]
+    fn test_retarget_only_applies_on_window_boundary() {
+        let baseline = target_from_difficulty_bits(32);
+        let chain = synthetic_chain_with_spacing(baseline, 1);
+
+        let mid_window_target = Blockchain::target_for_height(&chain, RETARGET_WINDOW - 1);
+        assert_eq!(mid_window_target, baseline, "target should be unchanged except on a retarget boundary");
+    }
+
+    #[test]
+    fn test_validate_timestamp_rejects_backdated_block() {
+        let chain = synthetic_chain_with_spacing(target_from_difficulty_bits(8), 100);
+        let height = chain.len();
+        let mtp = Blockchain::median_time_past(&chain, height);
+
+        assert!(!Blockchain::validate_timestamp(&chain, height, mtp));
+        assert!(!Blockchain::validate_timestamp(&chain, height, mtp - 1));
+    }
+
+    #[test]
+    fn test_validate_timestamp_rejects_far_future_block() {
+        let chain = synthetic_chain_with_spacing(target_from_difficulty_bits(8), 100);
+        let height = chain.len();
+
+        assert!(!Blockchain::validate_timestamp(&chain, height, now() + FUTURE_TIME_LIMIT_SECS + 60));
+    }
+
+    #[test]
+    fn test_validate_timestamp_accepts_reasonable_block() {
+        let chain = synthetic_chain_with_spacing(target_from_difficulty_bits(8), 100);
+        let height = chain.len();
+
+        assert!(Blockchain::validate_timestamp(&chain, height, now()));
+    }
+
     #[test]
     fn test_blockchain_integrity() {
         let mut blockchain = Blockchain::new();
-        blockchain.add_block(String::from("First Block"));
-        blockchain.add_block(String::from("Second Block"));
+        blockchain.add_block(sample_transactions());
+        blockchain.add_block(sample_transactions());
 
         assert!(blockchain.validate_chain(), "Blockchain integrity compromised!");
     }
+
+    #[test]
+    fn test_tampered_transactions_fail_merkle_check() {
+        let mut blockchain = Blockchain::new();
+        blockchain.add_block(sample_transactions());
+
+        // Mutate the transaction list in place without re-mining, so the stored
+        // `hash` still matches `calculate_hash()` (it only covers the header,
+        // including the now-stale `merkle_root`) but the transactions no longer
+        // hash to that root.
+        let tip_hash = blockchain.best_tip.clone();
+        let tampered_block = blockchain.blocks.get_mut(&tip_hash).unwrap();
+        let (_, mallory_key) = keypair_from_seed("mallory");
+        tampered_block.transactions.push(Transaction {
+            sender: mallory_key,
+            receiver: mallory_key,
+            amount: 1_000_000.0,
+            signature: None,
+        });
+
+        assert_eq!(tampered_block.hash, tampered_block.calculate_hash());
+        assert!(!blockchain.validate_chain(), "tampered transactions should fail Merkle verification");
+    }
+
+    #[test]
+    fn test_sign_and_verify_signature_roundtrip() {
+        let (alice_secret, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+        let mut transaction = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+
+        transaction.sign(&alice_secret);
+
+        assert!(transaction.verify_signature());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_missing_signature() {
+        let (_, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+        let transaction = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+
+        assert!(!transaction.verify_signature());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_tampered_amount() {
+        let (alice_secret, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+        let mut transaction = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+        transaction.sign(&alice_secret);
+
+        transaction.amount = 1_000_000.0;
+
+        assert!(!transaction.verify_signature(), "signature shouldn't verify once the signed amount changes");
+    }
+
+    #[test]
+    fn test_accept_block_rejects_unsigned_transaction() {
+        let mut blockchain = Blockchain::new();
+        let active_chain = blockchain.active_chain();
+        let height = active_chain.len();
+        let (_, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+
+        let unsigned = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+        let mut block = Block::new(height, Blockchain::next_valid_timestamp(&active_chain, height), blockchain.best_tip.clone(), vec![unsigned]);
+        block.mine(Blockchain::target_for_height(&active_chain, height));
+
+        assert!(!blockchain.accept_block(block), "an unsigned transaction must not be accepted");
+    }
+
+    #[test]
+    fn test_accept_block_rejects_overspend() {
+        let mut blockchain = Blockchain::new();
+
+        let (network_secret, network_key) = network_keypair();
+        let (alice_secret, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+
+        let mut fund_alice = Transaction { sender: network_key, receiver: alice_key, amount: 1.0, signature: None };
+        fund_alice.sign(&network_secret);
+        blockchain.add_block(vec![fund_alice]);
+
+        // Alice only holds 1.0; spending more than that must be rejected.
+        let active_chain = blockchain.active_chain();
+        let height = active_chain.len();
+        let mut overspend = Transaction { sender: alice_key, receiver: bob_key, amount: 2.0, signature: None };
+        overspend.sign(&alice_secret);
+        let mut block = Block::new(height, Blockchain::next_valid_timestamp(&active_chain, height), blockchain.best_tip.clone(), vec![overspend]);
+        block.mine(Blockchain::target_for_height(&active_chain, height));
+
+        assert!(!blockchain.accept_block(block), "spending more than the sender holds must be rejected");
+    }
+
+    // Builds a block on top of `chain` (genesis-first, ending at the intended
+    // parent) without paying for real mining: as long as the target stays
+    // `MAX_TARGET` (true below the first retarget boundary) any hash
+    // satisfies `check_pow`, so this only needs to mirror what `add_block`
+    // would have computed for height, target and timestamp.
+    fn child_block(chain: &[Block], previous_hash: &str, transactions: Vec<Transaction>) -> Block {
+        let height = chain.len();
+        let timestamp = Blockchain::next_valid_timestamp(chain, height);
+        let mut block = Block::new(height, timestamp, previous_hash.to_string(), transactions);
+        block.target = Blockchain::target_for_height(chain, height);
+        block.hash = block.calculate_hash();
+        block
+    }
+
+    fn rival_transactions() -> Vec<Transaction> {
+        let (network_secret, network_key) = network_keypair();
+        let (_, eve_key) = keypair_from_seed("eve");
+        let mut transaction = Transaction { sender: network_key, receiver: eve_key, amount: 5.0, signature: None };
+        transaction.sign(&network_secret);
+        vec![transaction]
+    }
+
+    #[test]
+    fn test_accept_block_extends_tip_without_reorg() {
+        let mut blockchain = Blockchain::new();
+        let genesis = blockchain.best_tip().clone();
+
+        let block_a = child_block(std::slice::from_ref(&genesis), &genesis.hash, sample_transactions());
+        let reorg = blockchain.accept_block(block_a.clone());
+
+        assert!(!reorg, "extending the current best tip is not a reorg");
+        assert_eq!(blockchain.best_tip().hash, block_a.hash);
+    }
+
+    #[test]
+    fn test_accept_block_rejects_unknown_parent() {
+        let mut blockchain = Blockchain::new();
+        let genesis = blockchain.best_tip().clone();
+
+        let orphan = child_block(std::slice::from_ref(&genesis), "deadbeef", sample_transactions());
+        let reorg = blockchain.accept_block(orphan);
+
+        assert!(!reorg);
+        assert_eq!(blockchain.best_tip().hash, genesis.hash, "unknown-parent block must not be accepted");
+    }
+
+    #[test]
+    fn test_fork_with_more_work_triggers_reorg() {
+        let mut blockchain = Blockchain::new();
+        let genesis = blockchain.best_tip().clone();
+
+        // Main branch: a single block on top of genesis.
+        let block_a = child_block(std::slice::from_ref(&genesis), &genesis.hash, sample_transactions());
+        assert!(!blockchain.accept_block(block_a.clone()));
+        assert_eq!(blockchain.best_tip().hash, block_a.hash);
+
+        // Competing branch forking from genesis: same work as block_a at first,
+        // so it must not dislodge the existing tip...
+        let block_b1 = child_block(std::slice::from_ref(&genesis), &genesis.hash, rival_transactions());
+        assert!(!blockchain.accept_block(block_b1.clone()), "equal work should not dislodge the current tip");
+        assert_eq!(blockchain.best_tip().hash, block_a.hash);
+
+        // ...until it's extended past block_a's cumulative work.
+        let block_b2 = child_block(&[genesis.clone(), block_b1.clone()], &block_b1.hash, rival_transactions());
+        let reorg = blockchain.accept_block(block_b2.clone());
+
+        assert!(reorg, "switching to the heavier branch should be reported as a reorg");
+        assert_eq!(blockchain.best_tip().hash, block_b2.hash);
+
+        let active_hashes: Vec<String> = blockchain.active_chain().iter().map(|b| b.hash.clone()).collect();
+        assert_eq!(active_hashes, vec![genesis.hash, block_b1.hash, block_b2.hash]);
+    }
+
+    fn many_transactions() -> Vec<Transaction> {
+        (0..5)
+            .map(|i| {
+                let (_, sender) = keypair_from_seed(&format!("sender-{}", i));
+                let (_, receiver) = keypair_from_seed(&format!("receiver-{}", i));
+                Transaction { sender, receiver, amount: i as f32, signature: None }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merkle_proof_verifies_included_transaction() {
+        let transactions = many_transactions();
+        let root = Transaction::create_merkle_root(&transactions);
+
+        for (index, transaction) in transactions.iter().enumerate() {
+            let proof = Transaction::generate_merkle_proof(&transactions, index);
+            assert!(
+                verify_proof(&transaction.transaction_hash(), &proof, &root),
+                "proof for transaction {} should verify against the root",
+                index
+            );
+        }
+    }
+
+    #[test]
+    fn test_merkle_proof_rejects_wrong_transaction() {
+        let transactions = many_transactions();
+        let root = Transaction::create_merkle_root(&transactions);
+        let proof = Transaction::generate_merkle_proof(&transactions, 0);
+
+        let (_, mallory_key) = keypair_from_seed("mallory");
+        let forged = Transaction {
+            sender: mallory_key,
+            receiver: mallory_key,
+            amount: 1_000_000.0,
+            signature: None,
+        };
+
+        assert!(!verify_proof(&forged.transaction_hash(), &proof, &root));
+    }
+
+    fn temp_file_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("rust_blockchain_test_{}_{}.dat", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_transaction_serialize_roundtrip() {
+        let (alice_secret, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+        let mut transaction = Transaction { sender: alice_key, receiver: bob_key, amount: 1.5, signature: None };
+        transaction.sign(&alice_secret);
+
+        let bytes = transaction.serialize();
+        let decoded = Transaction::deserialize(&bytes).expect("valid transaction bytes should deserialize");
+
+        assert_eq!(decoded.sender, transaction.sender);
+        assert_eq!(decoded.receiver, transaction.receiver);
+        assert_eq!(decoded.amount, transaction.amount);
+        assert!(decoded.verify_signature());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_truncated_bytes() {
+        let (alice_secret, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+        let mut transaction = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+        transaction.sign(&alice_secret);
+
+        let mut bytes = transaction.serialize();
+        bytes.truncate(bytes.len() - 1);
+
+        assert!(Transaction::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_trailing_bytes() {
+        let (alice_secret, alice_key) = keypair_from_seed("alice");
+        let (_, bob_key) = keypair_from_seed("bob");
+        let mut transaction = Transaction { sender: alice_key, receiver: bob_key, amount: 1.0, signature: None };
+        transaction.sign(&alice_secret);
+
+        let mut bytes = transaction.serialize();
+        bytes.push(0xaa);
+
+        assert!(Transaction::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_block_serialize_roundtrip() {
+        let block = Block::new(0, now(), String::from("0"), sample_transactions());
+
+        let bytes = block.serialize();
+        let decoded = Block::deserialize(&bytes).expect("valid block bytes should deserialize");
+
+        assert_eq!(decoded.hash, block.hash);
+        assert_eq!(decoded.merkle_root, block.merkle_root);
+        assert_eq!(decoded.previous_hash, block.previous_hash);
+        assert_eq!(decoded.target, block.target);
+        assert_eq!(decoded.transactions.len(), block.transactions.len());
+    }
+
+    #[test]
+    fn test_blockchain_serialize_roundtrip() {
+        let mut blockchain = Blockchain::new();
+        blockchain.add_block(sample_transactions());
+        blockchain.add_block(sample_transactions());
+
+        let bytes = blockchain.serialize();
+        let decoded = Blockchain::deserialize(&bytes).expect("valid blockchain bytes should deserialize");
+
+        assert_eq!(decoded.best_tip, blockchain.best_tip);
+        assert_eq!(decoded.active_chain().len(), blockchain.active_chain().len());
+        assert!(decoded.validate_chain());
+    }
+
+    #[test]
+    fn test_save_and_load_from_file_roundtrip() {
+        let mut blockchain = Blockchain::new();
+        blockchain.add_block(sample_transactions());
+
+        let path = temp_file_path("roundtrip");
+        blockchain.save_to_file(path.to_str().unwrap()).expect("save should succeed");
+        let loaded = Blockchain::load_from_file(path.to_str().unwrap()).expect("load should succeed and validate");
+
+        assert_eq!(loaded.best_tip, blockchain.best_tip);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_load_from_file_rejects_corrupted_data() {
+        let mut blockchain = Blockchain::new();
+        blockchain.add_block(sample_transactions());
+
+        let path = temp_file_path("corrupted");
+        let mut bytes = blockchain.serialize();
+        // Flip a byte in the middle of the encoding so the file no longer
+        // round-trips, whether that surfaces as a parse error or as a
+        // `validate_chain` failure.
+        let flip_index = bytes.len() / 2;
+        bytes[flip_index] ^= 0xff;
+        std::fs::write(&path, &bytes).expect("write should succeed");
+
+        let result = Blockchain::load_from_file(path.to_str().unwrap());
+
+        assert!(result.is_err(), "a tampered file should fail to load");
+        std::fs::remove_file(&path).ok();
+    }
 }